@@ -0,0 +1,230 @@
+use alloy::{
+    eips::BlockNumberOrTag,
+    providers::{Provider, ProviderBuilder},
+};
+use async_trait::async_trait;
+
+use crate::rpc;
+
+/// Number of trailing blocks sampled from `eth_feeHistory` when deriving
+/// priority-fee percentiles for [`GasCategory`].
+const FEE_HISTORY_BLOCK_COUNT: u64 = 20;
+
+/// Relative urgency for a gas price estimate, mirroring the slow/standard/fast
+/// tiers exposed by public gas oracles (e.g. ProposeGasPrice vs FastGasPrice).
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub enum GasCategory {
+    Slow,
+    Standard,
+    Fast,
+}
+
+impl GasCategory {
+    /// Reward percentile requested from `eth_feeHistory` for this category.
+    const fn reward_percentile(self) -> f64 {
+        match self {
+            Self::Slow => 10.0,
+            Self::Standard => 50.0,
+            Self::Fast => 90.0,
+        }
+    }
+}
+
+/// Source of the EVM priority-fee tip for a [`GasCategory`]. Returns `None`
+/// on failure instead of panicking, mirroring `price::PriceOracle`.
+/// `EvmFeeHistoryOracle` is the network-backed implementation; `FixedGasOracle`
+/// (test-only, below) is a second one, so callers that take `&dyn GasOracle`
+/// (see `priority_fee_tip`) are genuinely swappable rather than decorative.
+#[async_trait]
+pub trait GasOracle {
+    async fn fetch(&self, category: GasCategory) -> Option<u128>;
+}
+
+/// Averages the per-block reward samples `eth_feeHistory` returns for a
+/// single requested percentile, producing one priority-fee figure for that
+/// percentile across the sampled block range. Errors instead of panicking if
+/// a block's reward entry is shorter than the requested percentile count —
+/// third-party RPC aggregators aren't guaranteed to be spec-perfect, and an
+/// unhandled index here would crash the binary instead of letting
+/// `rpc::with_failover` move on to the next endpoint.
+fn average_reward(rewards: &[Vec<u128>]) -> anyhow::Result<u128> {
+    if rewards.is_empty() {
+        return Err(anyhow::anyhow!("eth_feeHistory returned no blocks"));
+    }
+
+    let mut sum: u128 = 0;
+    for block_rewards in rewards {
+        sum += block_rewards.first().ok_or_else(|| {
+            anyhow::anyhow!("eth_feeHistory block reward missing the requested percentile")
+        })?;
+    }
+
+    Ok(sum / rewards.len() as u128)
+}
+
+/// Derives slow/standard/fast priority-fee tips from `eth_feeHistory`, taking
+/// the percentile of the per-block reward samples matching the requested
+/// [`GasCategory`], and fails over across `rpc_http_urls` in order.
+pub struct EvmFeeHistoryOracle {
+    rpc_http_urls: &'static [&'static str],
+}
+
+impl EvmFeeHistoryOracle {
+    pub const fn new(rpc_http_urls: &'static [&'static str]) -> Self {
+        Self { rpc_http_urls }
+    }
+}
+
+#[async_trait]
+impl GasOracle for EvmFeeHistoryOracle {
+    /// Fetches the priority-fee tip for `category`. Returns `None` if every
+    /// configured RPC endpoint failed.
+    async fn fetch(&self, category: GasCategory) -> Option<u128> {
+        rpc::with_failover(self.rpc_http_urls, |endpoint| async move {
+            let client = ProviderBuilder::new().on_http(endpoint.parse()?);
+
+            let fee_history = client
+                .get_fee_history(
+                    FEE_HISTORY_BLOCK_COUNT,
+                    BlockNumberOrTag::Latest,
+                    &[category.reward_percentile()],
+                )
+                .await?;
+
+            let rewards = fee_history.reward.ok_or_else(|| {
+                anyhow::anyhow!("eth_feeHistory did not return reward percentiles")
+            })?;
+
+            average_reward(&rewards)
+        })
+        .await
+    }
+}
+
+/// Fetches the priority-fee tip for `category` from `oracle`. Takes any
+/// [`GasOracle`] as a trait object so the lookup is testable against a fixed tip instead of a
+/// real RPC endpoint; `EvmFeeHistoryOracle::estimate_eip1559_fees` below
+/// calls this with itself as the oracle.
+async fn priority_fee_tip(oracle: &dyn GasOracle, category: GasCategory) -> Option<u128> {
+    oracle.fetch(category).await
+}
+
+/// The base fee and priority-fee tip that together make up an EIP-1559
+/// transaction's effective gas price, reported separately so callers can see
+/// which component dominates the burn.
+pub struct Eip1559Fee {
+    pub base_fee_per_gas: u128,
+    pub priority_fee_per_gas: u128,
+}
+
+impl Eip1559Fee {
+    pub const fn total_per_gas(&self) -> u128 {
+        self.base_fee_per_gas + self.priority_fee_per_gas
+    }
+}
+
+/// Either a proper EIP-1559 estimate or a legacy single-number gas price,
+/// depending on whether the chain's latest block reports a base fee.
+pub enum EvmGasPrice {
+    Eip1559(Eip1559Fee),
+    Legacy(u128),
+}
+
+impl EvmGasPrice {
+    pub const fn per_gas(&self) -> u128 {
+        match self {
+            Self::Eip1559(fee) => fee.total_per_gas(),
+            Self::Legacy(price) => *price,
+        }
+    }
+}
+
+impl EvmFeeHistoryOracle {
+    /// Derives an EIP-1559 fee estimate from the latest block's base fee and
+    /// an `eth_feeHistory` priority-fee percentile. Returns `None` if every
+    /// endpoint reports no base fee (legacy pricing) or is unreachable.
+    pub async fn estimate_eip1559_fees(&self, category: GasCategory) -> Option<Eip1559Fee> {
+        let base_fee_per_gas = rpc::with_failover(self.rpc_http_urls, |endpoint| async move {
+            let client = ProviderBuilder::new().on_http(endpoint.parse()?);
+
+            let latest_block = client
+                .get_block_by_number(BlockNumberOrTag::Latest)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("node returned no latest block"))?;
+
+            latest_block
+                .header
+                .base_fee_per_gas
+                .map(u128::from)
+                .ok_or_else(|| anyhow::anyhow!("chain has no base fee (legacy pricing)"))
+        })
+        .await?;
+
+        let priority_fee_per_gas = priority_fee_tip(self, category).await?;
+
+        Some(Eip1559Fee {
+            base_fee_per_gas,
+            priority_fee_per_gas,
+        })
+    }
+
+    /// Falls back to a single legacy `eth_gasPrice` call, failing over across
+    /// `rpc_http_urls` in order. Returns `None` if every endpoint failed.
+    pub async fn legacy_gas_price(&self) -> Option<u128> {
+        rpc::with_failover(self.rpc_http_urls, |endpoint| async move {
+            let client = ProviderBuilder::new().on_http(endpoint.parse()?);
+            Ok(client.get_gas_price().await?)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{average_reward, priority_fee_tip, GasCategory, GasOracle};
+
+    struct FixedGasOracle(Option<u128>);
+
+    #[async_trait::async_trait]
+    impl GasOracle for FixedGasOracle {
+        async fn fetch(&self, _category: GasCategory) -> Option<u128> {
+            self.0
+        }
+    }
+
+    #[test]
+    fn averages_the_single_requested_percentile_across_blocks() {
+        let rewards = vec![vec![100], vec![200], vec![300]];
+
+        assert_eq!(average_reward(&rewards).unwrap(), 200);
+    }
+
+    #[test]
+    fn errors_when_eth_fee_history_sampled_no_blocks() {
+        assert!(average_reward(&[]).is_err());
+    }
+
+    #[test]
+    fn errors_when_a_block_reward_is_missing_the_requested_percentile() {
+        let rewards = vec![vec![100], vec![]];
+
+        assert!(average_reward(&rewards).is_err());
+    }
+
+    #[tokio::test]
+    async fn priority_fee_tip_reads_through_a_pluggable_gas_oracle() {
+        let oracle = FixedGasOracle(Some(42));
+
+        assert_eq!(
+            priority_fee_tip(&oracle, GasCategory::Standard).await,
+            Some(42)
+        );
+    }
+
+    #[tokio::test]
+    async fn priority_fee_tip_propagates_a_failed_oracle() {
+        let oracle = FixedGasOracle(None);
+
+        assert_eq!(priority_fee_tip(&oracle, GasCategory::Standard).await, None);
+    }
+}
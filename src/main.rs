@@ -1,19 +1,71 @@
-use alloy::providers::{Provider, ProviderBuilder};
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use near_jsonrpc_client::{methods, JsonRpcClient};
 use omni_types::ChainKind;
 
-const NEAR_RPC: &str = "https://rpc.mainnet.near.org";
-const BASE_RPC: &str = "https://base.llamarpc.com";
-const ARB_RPC: &str = "https://arbitrum.llamarpc.com";
+mod gas_oracle;
+mod output;
+mod price;
+mod rpc;
+
+use gas_oracle::{EvmFeeHistoryOracle, EvmGasPrice, GasCategory};
+use output::{ChainFeeReport, OutputFormat, UnavailableReason};
+use price::{CoinGeckoPriceOracle, PriceCache};
 
 const NEAR_FIN_TRANSFER_DEPOSIT: u128 = 600_000_000_000_000_000_000; // https://github.com/Near-One/bridge-sdk-rs/blob/78d96e8ba2c657d3860da46bbc0f02e9a013c1a0/bridge-sdk/bridge-clients/near-bridge-client/src/near_bridge_client.rs#L33
 
 const NEAR_GAS: u128 = 33_220_000_000_000; // https://nearblocks.io/txns/7L6J5qi3Yqabb8i8KrtixN5ujyoswrSzW9egjFuGD8Vv
+// Mainnet finalize-transfer gas is noticeably higher than on Base/Arb: it's a
+// typed (EIP-2718) transaction carrying an access list, and L1 calldata isn't
+// discounted the way it is on the L2s.
+const ETH_GAS: u128 = 166_845;
 const BASE_GAS: u128 = 127_652; // https://basescan.org/tx/0xa779997b00a73277bc90dda525e61cf8fb919fd1f2c347cc370f720745e0c21b
 const ARB_GAS: u128 = 149_503; // https://arbiscan.io/tx/0x179c58a791909f5e1ac328aa3c810bde916dd3a9070205f6b56758404188fb8d
 const SOLANA_GAS: u64 = 103_372; // https://solscan.io/tx/35V7H2BGsyEPw3v2hMzjmQYTC4PwTmu8bY7LiNm2UFMfGhfe86eZPLsKpQFyqsq9vs7HtBrLqFfBUPvLtPW4Qed
 
+/// Default measured gas units for an EVM chain, before any `--gas-units`
+/// override is applied.
+const fn default_gas_units(chain: ChainKind) -> u128 {
+    match chain {
+        ChainKind::Eth => ETH_GAS,
+        ChainKind::Base => BASE_GAS,
+        ChainKind::Arb => ARB_GAS,
+        ChainKind::Near | ChainKind::Sol => {
+            unreachable!("default_gas_units is only defined for EVM chains")
+        }
+    }
+}
+
+/// Parses a single `--gas-units <chain>=<n>` argument. Rejects non-EVM
+/// chains up front, since `resolve_gas_units` only ever looks these up for
+/// Eth/Base/Arb and a typo'd chain would otherwise be silently ignored.
+fn parse_gas_units_override(raw: &str) -> Result<(ChainKind, u128), String> {
+    let (chain, units) = raw
+        .split_once('=')
+        .ok_or_else(|| format!("expected `<chain>=<n>`, got `{raw}`"))?;
+
+    let chain = ChainKind::from_str(chain, true).map_err(|_| format!("unknown chain `{chain}`"))?;
+    if !matches!(chain, ChainKind::Eth | ChainKind::Base | ChainKind::Arb) {
+        return Err(format!(
+            "`--gas-units` only applies to EVM chains (eth, base, arb), got `{chain:?}`"
+        ));
+    }
+
+    let units = units
+        .parse::<u128>()
+        .map_err(|_| format!("`{units}` is not a valid gas unit count"))?;
+
+    Ok((chain, units))
+}
+
+/// Resolves the gas units to use for `chain`, preferring a matching
+/// `--gas-units` override over the hard-coded constant.
+fn resolve_gas_units(chain: ChainKind, overrides: &[(ChainKind, u128)]) -> u128 {
+    overrides
+        .iter()
+        .find(|(override_chain, _)| *override_chain == chain)
+        .map_or_else(|| default_gas_units(chain), |(_, units)| *units)
+}
+
 #[derive(Parser, Debug)]
 struct Args {
     #[arg(
@@ -31,110 +83,288 @@ struct Args {
         default_value = "usd"
     )]
     currency: String,
+    #[arg(
+        short,
+        long,
+        help = "How urgently the transfer needs to land, which affects the estimated gas price",
+        default_value = "standard"
+    )]
+    speed: GasCategory,
+    #[arg(
+        short,
+        long,
+        help = "Output format: human-readable text or JSON for programmatic consumption",
+        default_value = "text"
+    )]
+    format: OutputFormat,
+    #[arg(
+        long,
+        help = "Override the measured gas units for an EVM chain, e.g. --gas-units base=150000 (repeatable)",
+        value_name = "CHAIN=N",
+        value_parser = parse_gas_units_override
+    )]
+    gas_units: Vec<(ChainKind, u128)>,
 }
 
-async fn get_token_price(chain: ChainKind, currency: &str) -> f64 {
-    let token = match chain {
-        ChainKind::Near => "near",
-        ChainKind::Eth | ChainKind::Base | ChainKind::Arb => "ethereum",
-        ChainKind::Sol => "solana",
-    };
-
-    let url = format!(
-        "https://api.coingecko.com/api/v3/simple/price?ids={token}&vs_currencies={currency}"
-    );
-
-    let response = reqwest::get(&url)
-        .await
-        .unwrap()
-        .json::<serde_json::Value>()
-        .await
-        .unwrap();
+async fn get_near_gas_price() -> Option<u128> {
+    rpc::with_failover(rpc::NEAR_RPC_ENDPOINTS, |endpoint| async move {
+        let client = JsonRpcClient::connect(endpoint);
+        let request = methods::gas_price::RpcGasPriceRequest { block_id: None };
 
-    response[token][currency].as_f64().unwrap()
+        Ok(client.call(request).await?.gas_price)
+    })
+    .await
 }
 
-async fn get_near_gas_price() -> u128 {
-    let client = JsonRpcClient::connect(NEAR_RPC);
-    let request = methods::gas_price::RpcGasPriceRequest { block_id: None };
-
-    client.call(request).await.unwrap().gas_price
-}
+async fn get_near_fees(
+    amount: u128,
+    currency: &str,
+    _speed: GasCategory,
+    token_price: Option<f64>,
+) -> ChainFeeReport {
+    // NEAR's gas price is a protocol-determined value shared by the whole
+    // network rather than a market-driven tip, so it has no slow/fast tiers
+    // the way EVM priority fees do; `_speed` is accepted only so the CLI flag
+    // applies uniformly across chains.
+    let Some(gas_price) = get_near_gas_price().await else {
+        return ChainFeeReport::unavailable("NEAR", amount, currency, UnavailableReason::Rpc);
+    };
+    let Some(token_price) = token_price else {
+        return ChainFeeReport::unavailable("NEAR", amount, currency, UnavailableReason::Price);
+    };
 
-async fn get_near_fees(amount: u128, currency: &str) {
-    let total_near = ((get_near_gas_price().await * NEAR_GAS + NEAR_FIN_TRANSFER_DEPOSIT) * amount)
-        as f64
-        / 1e24;
+    let total_near = ((gas_price * NEAR_GAS + NEAR_FIN_TRANSFER_DEPOSIT) * amount) as f64 / 1e24;
+    let fiat_value = total_near * token_price;
 
-    println!(
-        "{} transfers to NEAR will burn {:.3} NEARs (approx. {:.3} {})",
+    ChainFeeReport::available(
+        "NEAR",
+        "NEAR",
+        gas_price,
+        NEAR_GAS,
         amount,
         total_near,
-        total_near * get_token_price(ChainKind::Near, currency).await,
-        currency
-    );
+        currency,
+        fiat_value,
+        None,
+    )
 }
 
-async fn get_evm_gas_price(chain: ChainKind) -> u128 {
-    let rpc_http_url = match chain {
-        ChainKind::Base => BASE_RPC,
-        ChainKind::Arb => ARB_RPC,
-        _ => unreachable!("Invalid chain was provided to `get_evm_gas_price` function (only Base and Arb is supported for now)"),
+async fn get_evm_gas_price(chain: ChainKind, speed: GasCategory) -> Option<EvmGasPrice> {
+    let rpc_http_urls = match chain {
+        ChainKind::Eth => rpc::ETH_RPC_ENDPOINTS,
+        ChainKind::Base => rpc::BASE_RPC_ENDPOINTS,
+        ChainKind::Arb => rpc::ARB_RPC_ENDPOINTS,
+        _ => unreachable!("Invalid chain was provided to `get_evm_gas_price` function (only Eth, Base and Arb is supported for now)"),
     };
 
-    let client = ProviderBuilder::new().on_http(rpc_http_url.parse().unwrap());
+    let oracle = EvmFeeHistoryOracle::new(rpc_http_urls);
+
+    if let Some(fee) = oracle.estimate_eip1559_fees(speed).await {
+        return Some(EvmGasPrice::Eip1559(fee));
+    }
 
-    client.get_gas_price().await.unwrap()
+    eprintln!(
+        "estimate_eip1559_fees not supported for {chain:?}, falling back to legacy gas price"
+    );
+
+    oracle.legacy_gas_price().await.map(EvmGasPrice::Legacy)
 }
 
-async fn get_evm_fees(chain: ChainKind, amount: u128, currency: &str) {
-    let gas = match chain {
-        ChainKind::Base => BASE_GAS,
-        ChainKind::Arb => ARB_GAS,
-        _ => unreachable!("Invalid chain was provided to `get_evm_fees` function (only Base and Arb is supported for now)"),
+async fn get_evm_fees(
+    chain: ChainKind,
+    amount: u128,
+    currency: &str,
+    speed: GasCategory,
+    token_price: Option<f64>,
+    gas: u128,
+) -> ChainFeeReport {
+    let chain_name = format!("{chain:?}");
+
+    let Some(gas_price) = get_evm_gas_price(chain, speed).await else {
+        return ChainFeeReport::unavailable(&chain_name, amount, currency, UnavailableReason::Rpc);
     };
+    let Some(token_price) = token_price else {
+        return ChainFeeReport::unavailable(
+            &chain_name,
+            amount,
+            currency,
+            UnavailableReason::Price,
+        );
+    };
+
+    let total_eth = (gas_price.per_gas() * gas * amount) as f64 / 1e18;
+    let fiat_value = total_eth * token_price;
 
-    let total_eth = (get_evm_gas_price(chain).await * gas * amount) as f64 / 1e18;
+    let note = match &gas_price {
+        EvmGasPrice::Eip1559(fee) => Some(format!(
+            "base fee {} wei/gas, priority fee {} wei/gas",
+            fee.base_fee_per_gas, fee.priority_fee_per_gas
+        )),
+        EvmGasPrice::Legacy(legacy_price) => {
+            Some(format!("legacy gas price {legacy_price} wei/gas"))
+        }
+    };
 
-    println!(
-        "{} transfers to {:?} will burn {:.3} ETHs (approx. {:.3} {})",
+    ChainFeeReport::available(
+        &chain_name,
+        "ETH",
+        gas_price.per_gas(),
+        gas,
         amount,
-        chain,
         total_eth,
-        total_eth * get_token_price(chain, currency).await,
-        currency
-    );
+        currency,
+        fiat_value,
+        note,
+    )
 }
 
-async fn get_solana_fees(amount: u128, currency: &str) {
+async fn get_solana_fees(amount: u128, currency: &str, token_price: Option<f64>) -> ChainFeeReport {
+    let Some(token_price) = token_price else {
+        return ChainFeeReport::unavailable("Solana", amount, currency, UnavailableReason::Price);
+    };
+
     let total_sol = (SOLANA_GAS as u128 * amount) as f64 / 1e9;
+    let fiat_value = total_sol * token_price;
 
-    println!(
-        "{} transfers to Solana will burn {:.6} SOLs (approx. {:.3} {})",
+    // Solana charges a flat lamports-per-signature fee rather than a
+    // gas-price * gas-units model, so `gas_units` is just 1.
+    ChainFeeReport::available(
+        "Solana",
+        "SOL",
+        u128::from(SOLANA_GAS),
+        1,
         amount,
         total_sol,
-        total_sol * get_token_price(ChainKind::Sol, currency).await,
-        currency
-    );
+        currency,
+        fiat_value,
+        None,
+    )
 }
 
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
 
+    let oracle = CoinGeckoPriceOracle;
+
     match args.destination_chain {
-        Some(ChainKind::Near) => get_near_fees(args.amount, &args.currency).await,
-        Some(ChainKind::Eth) => {
-            eprintln!("Fee calculation for Ethereum chain is not supported yet");
+        Some(chain @ ChainKind::Near) => {
+            let prices = PriceCache::fetch(&oracle, &[chain], &args.currency).await;
+            let report =
+                get_near_fees(args.amount, &args.currency, args.speed, prices.get(chain)).await;
+            output::print_single(&report, args.format);
+        }
+        Some(chain @ (ChainKind::Eth | ChainKind::Base | ChainKind::Arb)) => {
+            let prices = PriceCache::fetch(&oracle, &[chain], &args.currency).await;
+            let gas = resolve_gas_units(chain, &args.gas_units);
+            let report = get_evm_fees(
+                chain,
+                args.amount,
+                &args.currency,
+                args.speed,
+                prices.get(chain),
+                gas,
+            )
+            .await;
+            output::print_single(&report, args.format);
+        }
+        Some(chain @ ChainKind::Sol) => {
+            let prices = PriceCache::fetch(&oracle, &[chain], &args.currency).await;
+            let report = get_solana_fees(args.amount, &args.currency, prices.get(chain)).await;
+            output::print_single(&report, args.format);
         }
-        Some(ChainKind::Base) => get_evm_fees(ChainKind::Base, args.amount, &args.currency).await,
-        Some(ChainKind::Arb) => get_evm_fees(ChainKind::Arb, args.amount, &args.currency).await,
-        Some(ChainKind::Sol) => get_solana_fees(args.amount, &args.currency).await,
         None => {
-            get_near_fees(args.amount, &args.currency).await;
-            get_evm_fees(ChainKind::Base, args.amount, &args.currency).await;
-            get_evm_fees(ChainKind::Arb, args.amount, &args.currency).await;
-            get_solana_fees(args.amount, &args.currency).await;
+            let chains = [
+                ChainKind::Near,
+                ChainKind::Eth,
+                ChainKind::Base,
+                ChainKind::Arb,
+                ChainKind::Sol,
+            ];
+            let prices = PriceCache::fetch(&oracle, &chains, &args.currency).await;
+
+            let reports = vec![
+                get_near_fees(
+                    args.amount,
+                    &args.currency,
+                    args.speed,
+                    prices.get(ChainKind::Near),
+                )
+                .await,
+                get_evm_fees(
+                    ChainKind::Eth,
+                    args.amount,
+                    &args.currency,
+                    args.speed,
+                    prices.get(ChainKind::Eth),
+                    resolve_gas_units(ChainKind::Eth, &args.gas_units),
+                )
+                .await,
+                get_evm_fees(
+                    ChainKind::Base,
+                    args.amount,
+                    &args.currency,
+                    args.speed,
+                    prices.get(ChainKind::Base),
+                    resolve_gas_units(ChainKind::Base, &args.gas_units),
+                )
+                .await,
+                get_evm_fees(
+                    ChainKind::Arb,
+                    args.amount,
+                    &args.currency,
+                    args.speed,
+                    prices.get(ChainKind::Arb),
+                    resolve_gas_units(ChainKind::Arb, &args.gas_units),
+                )
+                .await,
+                get_solana_fees(args.amount, &args.currency, prices.get(ChainKind::Sol)).await,
+            ];
+            output::print_all(&reports, args.format);
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{default_gas_units, parse_gas_units_override, resolve_gas_units, ChainKind};
+
+    #[test]
+    fn parses_a_valid_evm_override() {
+        assert_eq!(
+            parse_gas_units_override("base=150000"),
+            Ok((ChainKind::Base, 150_000))
+        );
+    }
+
+    #[test]
+    fn rejects_a_missing_equals_sign() {
+        assert!(parse_gas_units_override("base150000").is_err());
+    }
+
+    #[test]
+    fn rejects_non_evm_chains() {
+        assert!(parse_gas_units_override("near=100").is_err());
+        assert!(parse_gas_units_override("sol=100").is_err());
+    }
+
+    #[test]
+    fn rejects_a_non_numeric_units_value() {
+        assert!(parse_gas_units_override("base=not-a-number").is_err());
+    }
+
+    #[test]
+    fn resolve_prefers_a_matching_override_over_the_default() {
+        let overrides = [(ChainKind::Base, 1)];
+
+        assert_eq!(resolve_gas_units(ChainKind::Base, &overrides), 1);
+    }
+
+    #[test]
+    fn resolve_falls_back_to_the_default_when_no_override_matches() {
+        assert_eq!(
+            resolve_gas_units(ChainKind::Eth, &[(ChainKind::Base, 1)]),
+            default_gas_units(ChainKind::Eth)
+        );
+    }
+}
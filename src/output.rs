@@ -0,0 +1,202 @@
+/// Output mode for reported fees: human-readable text, or JSON for
+/// programmatic consumption by a relayer's automated fee-setting logic (an
+/// array of reports for the all-chains path, a single report object when one
+/// destination chain is given).
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// Why a chain's fee estimate couldn't be computed, so callers (and JSON
+/// consumers) can tell a dead RPC endpoint apart from a price-oracle miss
+/// instead of seeing the same generic "unavailable" status for both.
+#[derive(Clone, Copy, Debug, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnavailableReason {
+    /// Every configured RPC endpoint for the chain failed.
+    Rpc,
+    /// The price oracle returned no quote for the chain's token.
+    Price,
+}
+
+/// The fee estimate for a single chain, serializable as-is for `--format json`.
+#[derive(serde::Serialize)]
+pub struct ChainFeeReport {
+    pub chain: String,
+    pub available: bool,
+    pub unavailable_reason: Option<UnavailableReason>,
+    pub token_symbol: Option<String>,
+    pub gas_price: Option<u128>,
+    pub gas_units: Option<u128>,
+    pub amount: u128,
+    pub native_burn: Option<f64>,
+    pub fiat_currency: String,
+    pub fiat_value: Option<f64>,
+    /// Extra diagnostics (e.g. EIP-1559 base fee/priority fee breakdown) that
+    /// don't fit the flat numeric fields above.
+    pub note: Option<String>,
+}
+
+impl ChainFeeReport {
+    #[allow(clippy::too_many_arguments)]
+    pub fn available(
+        chain: &str,
+        token_symbol: &str,
+        gas_price: u128,
+        gas_units: u128,
+        amount: u128,
+        native_burn: f64,
+        fiat_currency: &str,
+        fiat_value: f64,
+        note: Option<String>,
+    ) -> Self {
+        Self {
+            chain: chain.to_string(),
+            available: true,
+            unavailable_reason: None,
+            token_symbol: Some(token_symbol.to_string()),
+            gas_price: Some(gas_price),
+            gas_units: Some(gas_units),
+            amount,
+            native_burn: Some(native_burn),
+            fiat_currency: fiat_currency.to_string(),
+            fiat_value: Some(fiat_value),
+            note,
+        }
+    }
+
+    pub fn unavailable(
+        chain: &str,
+        amount: u128,
+        fiat_currency: &str,
+        reason: UnavailableReason,
+    ) -> Self {
+        Self {
+            chain: chain.to_string(),
+            available: false,
+            unavailable_reason: Some(reason),
+            token_symbol: None,
+            gas_price: None,
+            gas_units: None,
+            amount,
+            native_burn: None,
+            fiat_currency: fiat_currency.to_string(),
+            fiat_value: None,
+            note: None,
+        }
+    }
+
+    /// Renders this report as one plain-text line (burn to 6 decimals, fiat
+    /// value to 3), followed by the note in brackets when there is one.
+    pub fn print_text(&self) {
+        if !self.available {
+            let reason = match self.unavailable_reason {
+                Some(UnavailableReason::Rpc) => "all configured RPC endpoints failed",
+                Some(UnavailableReason::Price) => {
+                    "the price oracle returned no quote for its token"
+                }
+                None => "unknown reason",
+            };
+
+            println!("{} is unavailable: {reason}", self.chain);
+            return;
+        }
+
+        print!(
+            "{} transfers to {} will burn {:.6} {}s (approx. {:.3} {})",
+            self.amount,
+            self.chain,
+            self.native_burn.unwrap_or_default(),
+            self.token_symbol.as_deref().unwrap_or_default(),
+            self.fiat_value.unwrap_or_default(),
+            self.fiat_currency
+        );
+
+        match &self.note {
+            Some(note) => println!(" [{note}]"),
+            None => println!(),
+        }
+    }
+}
+
+pub fn print_single(report: &ChainFeeReport, format: OutputFormat) {
+    match format {
+        OutputFormat::Text => report.print_text(),
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(report).unwrap());
+        }
+    }
+}
+
+pub fn print_all(reports: &[ChainFeeReport], format: OutputFormat) {
+    match format {
+        OutputFormat::Text => reports.iter().for_each(ChainFeeReport::print_text),
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(reports).unwrap());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChainFeeReport, UnavailableReason};
+
+    #[test]
+    fn available_report_serializes_with_the_documented_fields() {
+        let report = ChainFeeReport::available(
+            "Eth",
+            "ETH",
+            1,
+            2,
+            3,
+            4.0,
+            "usd",
+            5.0,
+            Some("note".to_string()),
+        );
+
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "chain": "Eth",
+                "available": true,
+                "unavailable_reason": null,
+                "token_symbol": "ETH",
+                "gas_price": 1,
+                "gas_units": 2,
+                "amount": 3,
+                "native_burn": 4.0,
+                "fiat_currency": "usd",
+                "fiat_value": 5.0,
+                "note": "note"
+            })
+        );
+    }
+
+    #[test]
+    fn unavailable_report_serializes_with_its_reason() {
+        let report = ChainFeeReport::unavailable("Eth", 3, "usd", UnavailableReason::Rpc);
+
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "chain": "Eth",
+                "available": false,
+                "unavailable_reason": "rpc",
+                "token_symbol": null,
+                "gas_price": null,
+                "gas_units": null,
+                "amount": 3,
+                "native_burn": null,
+                "fiat_currency": "usd",
+                "fiat_value": null,
+                "note": null
+            })
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use omni_types::ChainKind;
+
+/// Source of token-to-fiat prices for a batch of CoinGecko-style token ids.
+/// Returns `None` on failure instead of panicking, so callers can fall back
+/// cleanly (e.g. report the chain as unavailable for lack of a price) rather
+/// than crash.
+#[async_trait]
+pub trait PriceOracle {
+    async fn fetch_prices(
+        &self,
+        token_ids: &[String],
+        currency: &str,
+    ) -> Option<HashMap<String, f64>>;
+}
+
+/// Default oracle: a single batched `simple/price` call against CoinGecko.
+pub struct CoinGeckoPriceOracle;
+
+#[async_trait]
+impl PriceOracle for CoinGeckoPriceOracle {
+    async fn fetch_prices(
+        &self,
+        token_ids: &[String],
+        currency: &str,
+    ) -> Option<HashMap<String, f64>> {
+        if token_ids.is_empty() {
+            return Some(HashMap::new());
+        }
+
+        let ids = token_ids.join(",");
+        let url = format!(
+            "https://api.coingecko.com/api/v3/simple/price?ids={ids}&vs_currencies={currency}"
+        );
+
+        let response = reqwest::get(&url)
+            .await
+            .ok()?
+            .json::<serde_json::Value>()
+            .await
+            .ok()?;
+
+        // A per-id lookup failure (missing from the response, malformed
+        // entry, ...) should only drop that one token, not the whole batch —
+        // collecting into `Option<HashMap<_, _>>` would zero out pricing for
+        // every chain in the run over a single bad id.
+        Some(
+            token_ids
+                .iter()
+                .filter_map(|id| {
+                    let price = response.get(id)?.get(currency)?.as_f64()?;
+                    Some((id.clone(), price))
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Fixed-price override, useful for testing and for environments where
+/// CoinGecko is geo-blocked.
+pub struct FixedPriceOracle {
+    prices: HashMap<String, f64>,
+}
+
+impl FixedPriceOracle {
+    pub fn new(prices: HashMap<String, f64>) -> Self {
+        Self { prices }
+    }
+}
+
+#[async_trait]
+impl PriceOracle for FixedPriceOracle {
+    async fn fetch_prices(
+        &self,
+        token_ids: &[String],
+        _currency: &str,
+    ) -> Option<HashMap<String, f64>> {
+        Some(
+            token_ids
+                .iter()
+                .filter_map(|id| self.prices.get(id).map(|price| (id.clone(), *price)))
+                .collect(),
+        )
+    }
+}
+
+/// The CoinGecko token id used to price `chain`'s native token.
+pub const fn token_id(chain: ChainKind) -> &'static str {
+    match chain {
+        ChainKind::Near => "near",
+        ChainKind::Eth | ChainKind::Base | ChainKind::Arb => "ethereum",
+        ChainKind::Sol => "solana",
+    }
+}
+
+/// Prices for a run, fetched once for the distinct token ids backing
+/// `chains` rather than once per chain (so Eth/Base/Arb, which all price off
+/// `ethereum`, only hit the oracle a single time).
+pub struct PriceCache {
+    prices: HashMap<String, f64>,
+}
+
+impl PriceCache {
+    pub async fn fetch(oracle: &dyn PriceOracle, chains: &[ChainKind], currency: &str) -> Self {
+        let mut token_ids: Vec<String> = chains
+            .iter()
+            .map(|&chain| token_id(chain).to_string())
+            .collect();
+        token_ids.sort_unstable();
+        token_ids.dedup();
+
+        let prices = oracle
+            .fetch_prices(&token_ids, currency)
+            .await
+            .unwrap_or_default();
+
+        Self { prices }
+    }
+
+    pub fn get(&self, chain: ChainKind) -> Option<f64> {
+        self.prices.get(token_id(chain)).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::{async_trait, token_id, ChainKind, FixedPriceOracle, HashMap, PriceCache, PriceOracle};
+
+    struct RecordingOracle {
+        requested_ids: Mutex<Vec<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl PriceOracle for RecordingOracle {
+        async fn fetch_prices(
+            &self,
+            token_ids: &[String],
+            _currency: &str,
+        ) -> Option<HashMap<String, f64>> {
+            self.requested_ids.lock().unwrap().push(token_ids.to_vec());
+            Some(token_ids.iter().map(|id| (id.clone(), 1.0)).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn price_cache_dedupes_shared_token_ids_into_one_call() {
+        let oracle = RecordingOracle {
+            requested_ids: Mutex::new(Vec::new()),
+        };
+        let chains = [
+            ChainKind::Eth,
+            ChainKind::Base,
+            ChainKind::Arb,
+            ChainKind::Near,
+        ];
+
+        let cache = PriceCache::fetch(&oracle, &chains, "usd").await;
+
+        let requests = oracle.requested_ids.lock().unwrap();
+        assert_eq!(requests.len(), 1, "expected a single batched request");
+        assert_eq!(
+            requests[0],
+            vec![
+                token_id(ChainKind::Eth).to_string(),
+                token_id(ChainKind::Near).to_string()
+            ]
+        );
+        assert_eq!(cache.get(ChainKind::Eth), Some(1.0));
+        assert_eq!(cache.get(ChainKind::Base), Some(1.0));
+        assert_eq!(cache.get(ChainKind::Near), Some(1.0));
+    }
+
+    #[tokio::test]
+    async fn fixed_price_oracle_skips_unknown_ids_instead_of_failing_the_batch() {
+        let mut prices = HashMap::new();
+        prices.insert("near".to_string(), 5.0);
+        let oracle = FixedPriceOracle::new(prices);
+
+        let result = oracle
+            .fetch_prices(&["near".to_string(), "solana".to_string()], "usd")
+            .await
+            .unwrap();
+
+        assert_eq!(result.get("near"), Some(&5.0));
+        assert!(!result.contains_key("solana"));
+    }
+}
@@ -0,0 +1,96 @@
+use std::{future::Future, time::Duration};
+
+/// How long a single RPC call is given to respond before it's considered
+/// failed and the next endpoint in line is tried.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub const NEAR_RPC_ENDPOINTS: &[&str] = &[
+    "https://rpc.mainnet.near.org",
+    "https://near.lava.build",
+    "https://near.drpc.org",
+];
+
+pub const ETH_RPC_ENDPOINTS: &[&str] = &[
+    "https://eth.llamarpc.com",
+    "https://ethereum-rpc.publicnode.com",
+    "https://eth.drpc.org",
+];
+
+pub const BASE_RPC_ENDPOINTS: &[&str] = &[
+    "https://base.llamarpc.com",
+    "https://base-rpc.publicnode.com",
+    "https://base.drpc.org",
+];
+
+pub const ARB_RPC_ENDPOINTS: &[&str] = &[
+    "https://arbitrum.llamarpc.com",
+    "https://arbitrum-one-rpc.publicnode.com",
+    "https://arbitrum.drpc.org",
+];
+
+/// Tries `request` against each of `endpoints` in order, moving on to the
+/// next one on timeout or error, and returning `None` only once every
+/// endpoint has been exhausted.
+pub async fn with_failover<T, F, Fut>(endpoints: &[&'static str], mut request: F) -> Option<T>
+where
+    F: FnMut(&'static str) -> Fut,
+    Fut: Future<Output = anyhow::Result<T>>,
+{
+    for &endpoint in endpoints {
+        match tokio::time::timeout(REQUEST_TIMEOUT, request(endpoint)).await {
+            Ok(Ok(value)) => return Some(value),
+            Ok(Err(err)) => eprintln!("RPC endpoint {endpoint} failed: {err}"),
+            Err(_) => eprintln!("RPC endpoint {endpoint} timed out"),
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::with_failover;
+
+    #[tokio::test]
+    async fn returns_none_when_every_endpoint_fails() {
+        let endpoints = ["a", "b", "c"];
+
+        let result: Option<()> =
+            with_failover(&endpoints, |_| async { Err(anyhow::anyhow!("down")) }).await;
+
+        assert_eq!(result, None);
+    }
+
+    #[tokio::test]
+    async fn short_circuits_once_the_first_endpoint_succeeds() {
+        let endpoints = ["a", "b", "c"];
+        let calls = AtomicUsize::new(0);
+
+        let result = with_failover(&endpoints, |_| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async { Ok(1) }
+        })
+        .await;
+
+        assert_eq!(result, Some(1));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn falls_over_to_the_second_endpoint_after_the_first_fails() {
+        let endpoints = ["a", "b"];
+
+        let result = with_failover(&endpoints, |endpoint| async move {
+            if endpoint == "a" {
+                Err(anyhow::anyhow!("down"))
+            } else {
+                Ok(2)
+            }
+        })
+        .await;
+
+        assert_eq!(result, Some(2));
+    }
+}